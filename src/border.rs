@@ -0,0 +1,154 @@
+use image::{DynamicImage, GenericImage, GenericImageView, Rgba};
+
+/// Per-side pixel widths of a border, or a margin expressed as a fraction of
+/// the image's longest side so the border scales with the export size.
+#[derive(Debug, Clone, Copy)]
+pub enum BorderWidth {
+    Sides {
+        top: u32,
+        right: u32,
+        bottom: u32,
+        left: u32,
+    },
+    MarginFraction(f32),
+}
+
+impl BorderWidth {
+    fn resolve(&self, longest_side: u32) -> (u32, u32, u32, u32) {
+        match *self {
+            BorderWidth::Sides {
+                top,
+                right,
+                bottom,
+                left,
+            } => (top, right, bottom, left),
+            BorderWidth::MarginFraction(fraction) => {
+                let margin = (longest_side as f32 * fraction).round() as u32;
+                (margin, margin, margin, margin)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BorderSpec {
+    pub color: Rgba<u8>,
+    pub width: BorderWidth,
+    pub corner_radius: u32,
+}
+
+impl BorderSpec {
+    /// A symmetric border of `spacing` pixels on every side, in opaque white.
+    pub fn uniform(spacing: u32) -> BorderSpec {
+        BorderSpec {
+            color: Rgba([255; 4]),
+            width: BorderWidth::Sides {
+                top: spacing,
+                right: spacing,
+                bottom: spacing,
+                left: spacing,
+            },
+            corner_radius: 0,
+        }
+    }
+}
+
+/// Parses a border color given as a `#rrggbb`/`#rrggbbaa` hex string or a
+/// small set of common names.
+pub fn parse_color(value: &str) -> Result<Rgba<u8>, String> {
+    let value = value.trim();
+
+    if let Some(hex) = value.strip_prefix('#') {
+        let channel = |i: usize| -> Result<u8, String> {
+            hex.get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or_else(|| format!("invalid hex color: {value}"))
+        };
+        return match hex.len() {
+            6 => Ok(Rgba([channel(0)?, channel(2)?, channel(4)?, 255])),
+            8 => Ok(Rgba([channel(0)?, channel(2)?, channel(4)?, channel(6)?])),
+            _ => Err(format!("invalid hex color: {value}")),
+        };
+    }
+
+    match value.to_lowercase().as_str() {
+        "white" => Ok(Rgba([255, 255, 255, 255])),
+        "black" => Ok(Rgba([0, 0, 0, 255])),
+        "red" => Ok(Rgba([255, 0, 0, 255])),
+        "green" => Ok(Rgba([0, 128, 0, 255])),
+        "blue" => Ok(Rgba([0, 0, 255, 255])),
+        "transparent" => Ok(Rgba([0, 0, 0, 0])),
+        _ => Err(format!("unknown color: {value}")),
+    }
+}
+
+/// Pads `image` with a border built from `spec`, then rounds and
+/// anti-aliases the outer corners when `spec.corner_radius` is non-zero.
+pub fn add_border(image: &DynamicImage, spec: &BorderSpec) -> DynamicImage {
+    let (width, height) = image.dimensions();
+    let longest_side = width.max(height);
+    let (top, right, bottom, left) = spec.width.resolve(longest_side);
+
+    let new_width = width + left + right;
+    let new_height = height + top + bottom;
+
+    let mut bordered = DynamicImage::new_rgba8(new_width, new_height);
+    for y in 0..new_height {
+        for x in 0..new_width {
+            bordered.put_pixel(x, y, spec.color);
+        }
+    }
+
+    for y in 0..height {
+        for x in 0..width {
+            bordered.put_pixel(x + left, y + top, image.get_pixel(x, y));
+        }
+    }
+
+    if spec.corner_radius > 0 {
+        round_corners(&mut bordered, spec.corner_radius);
+    }
+
+    bordered
+}
+
+/// Cuts the four outer corners of `image` to a quarter-circle of `radius`
+/// pixels, fading alpha over the last pixel of the arc for anti-aliasing.
+fn round_corners(image: &mut DynamicImage, radius: u32) {
+    let (width, height) = image.dimensions();
+    let radius = radius.min(width / 2).min(height / 2);
+    if radius == 0 {
+        return;
+    }
+    let r = radius as f32;
+
+    let corners = [
+        (0..radius, 0..radius, (radius, radius)),
+        (width - radius..width, 0..radius, (width - radius - 1, radius)),
+        (0..radius, height - radius..height, (radius, height - radius - 1)),
+        (
+            width - radius..width,
+            height - radius..height,
+            (width - radius - 1, height - radius - 1),
+        ),
+    ];
+
+    for (xs, ys, (cx, cy)) in corners {
+        for y in ys.clone() {
+            for x in xs.clone() {
+                let dx = x as f32 - cx as f32;
+                let dy = y as f32 - cy as f32;
+                let distance = (dx * dx + dy * dy).sqrt();
+
+                if distance <= r - 0.5 {
+                    continue;
+                }
+
+                let mut pixel = image.get_pixel(x, y);
+                let coverage = (r + 0.5 - distance).clamp(0.0, 1.0);
+                pixel.0[3] = (pixel.0[3] as f32 * coverage).round() as u8;
+                image.put_pixel(x, y, pixel);
+            }
+        }
+    }
+}
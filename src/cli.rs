@@ -1,6 +1,9 @@
 use clap::{Args, Parser, Subcommand};
 use serde::Serialize;
 
+use crate::overlay::{BlendMode, OverlayPosition};
+use crate::resampler::ResampleFilter;
+
 #[derive(Debug, Parser)]
 #[clap(name = "rustography", version)]
 pub struct App {
@@ -18,18 +21,48 @@ pub enum Command {
 
 #[derive(Debug, Args)]
 pub struct ImageArgs {
-    /// The input file
-    #[clap(long, short)]
-    pub input: std::path::PathBuf,
+    /// The input file(s). Accepts glob patterns (e.g. "scans/*.tif") and may be
+    /// repeated to process a whole roll in one go.
+    #[clap(long, short, required = true, num_args = 1..)]
+    pub input: Vec<std::path::PathBuf>,
 
-    /// The output file
+    /// The output file. When more than one input is given, this is treated as
+    /// the output directory instead.
     #[clap(long, short)]
     pub output: Option<std::path::PathBuf>,
 
-    /// Add a border to the image
+    /// Add a border to the image, optionally giving a uniform pixel spacing
     #[clap(long, short = 'a')]
     pub add_border: Option<Option<usize>>,
 
+    /// Border color, as a hex code (e.g. "#ffffff") or a common name
+    #[clap(long)]
+    pub border_color: Option<String>,
+
+    /// Border width on the top side, in pixels (overrides --add_border's default)
+    #[clap(long)]
+    pub border_top: Option<u32>,
+
+    /// Border width on the right side, in pixels (overrides --add_border's default)
+    #[clap(long)]
+    pub border_right: Option<u32>,
+
+    /// Border width on the bottom side, in pixels (overrides --add_border's default)
+    #[clap(long)]
+    pub border_bottom: Option<u32>,
+
+    /// Border width on the left side, in pixels (overrides --add_border's default)
+    #[clap(long)]
+    pub border_left: Option<u32>,
+
+    /// Border margin as a fraction of the image's longest side, overriding the fixed per-side widths
+    #[clap(long)]
+    pub border_margin: Option<f32>,
+
+    /// Corner radius for the bordered image's outer corners, in pixels
+    #[clap(long)]
+    pub border_radius: Option<u32>,
+
     /// Fill to a certain aspect ratio (default 1.0 1.0)
     #[clap(long, short = 'f', num_args = 2)]
     pub fill_to_aspect_ratio: Option<Vec<f32>>,
@@ -37,6 +70,42 @@ pub struct ImageArgs {
     /// Resize so the longest side has a given value
     #[clap(long, short = 'l')]
     pub longest_side: Option<Option<usize>>,
+
+    /// Resampling filter used for every resize
+    #[clap(long, value_enum, default_value_t = ResampleFilter::Lanczos3)]
+    pub filter: ResampleFilter,
+
+    /// B parameter of the Mitchell-Netravali filter (only used with --filter mitchell-netravali)
+    #[clap(long, default_value_t = 1.0 / 3.0)]
+    pub mitchell_b: f32,
+
+    /// C parameter of the Mitchell-Netravali filter (only used with --filter mitchell-netravali)
+    #[clap(long, default_value_t = 1.0 / 3.0)]
+    pub mitchell_c: f32,
+
+    /// Reduce the image to a true indexed-color palette of this many colors (max 256), written as an 8-bit indexed PNG. Only applied when the output is a .png file
+    #[clap(long)]
+    pub quantize: Option<usize>,
+
+    /// Apply Floyd-Steinberg error diffusion when quantizing, to avoid banding in gradients
+    #[clap(long, requires = "quantize")]
+    pub dither: bool,
+
+    /// Composite a logo, frame-number strip, or signature onto the output
+    #[clap(long)]
+    pub overlay: Option<std::path::PathBuf>,
+
+    /// Where to anchor the overlay on the image
+    #[clap(long, value_enum, default_value_t = OverlayPosition::BottomRight, requires = "overlay")]
+    pub overlay_position: OverlayPosition,
+
+    /// Overlay opacity, from 0.0 (invisible) to 1.0 (fully opaque)
+    #[clap(long, default_value_t = 1.0, requires = "overlay")]
+    pub overlay_opacity: f32,
+
+    /// How the overlay's colors combine with the image beneath it
+    #[clap(long, value_enum, default_value_t = BlendMode::Normal, requires = "overlay")]
+    pub blend_mode: BlendMode,
 }
 
 #[derive(Debug, Args)]
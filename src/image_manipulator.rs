@@ -1,5 +1,10 @@
 use image::{DynamicImage, GenericImage, GenericImageView, ImageError, Rgba};
 
+use crate::border::{self, BorderSpec};
+use crate::overlay::{self, OverlaySpec};
+use crate::quantize::{self, QuantizeOptions};
+use crate::resampler::{self, MitchellParams, ResampleFilter};
+
 #[derive(Default)]
 pub struct ImageManipulator {
     image: DynamicImage,
@@ -7,7 +12,11 @@ pub struct ImageManipulator {
 
     expected_ratio: Option<(f32, f32)>,
     expected_longest_side: Option<usize>,
-    expected_border_spacing: Option<usize>,
+    border: Option<BorderSpec>,
+    resampler: ResampleFilter,
+    mitchell_params: MitchellParams,
+    quantize: Option<QuantizeOptions>,
+    overlay: Option<OverlaySpec>,
 }
 
 impl ImageManipulator {
@@ -24,8 +33,8 @@ impl ImageManipulator {
         })
     }
 
-    pub fn add_border(mut self, spacing: usize) -> ImageManipulator {
-        self.expected_border_spacing = Some(spacing);
+    pub fn border(mut self, spec: BorderSpec) -> ImageManipulator {
+        self.border = Some(spec);
         self
     }
 
@@ -39,32 +48,56 @@ impl ImageManipulator {
         self
     }
 
-    fn try_add_border(&mut self) {
-        if let Some(border_spacing) = self.expected_border_spacing {
-            let (width, height) = self.image.dimensions();
-            let spacing = border_spacing as u32;
-            let new_width = width + (spacing * 2);
-            let new_height = height + (spacing * 2);
-
-            let mut bordered_image = self.image.clone().resize_exact(
-                new_width,
-                new_height,
-                image::imageops::FilterType::Lanczos3,
-            );
-            for y in 0..new_height {
-                for x in 0..new_width {
-                    bordered_image.put_pixel(x, y, Rgba([255; 4]));
-                }
+    pub fn resampler(mut self, filter: ResampleFilter) -> ImageManipulator {
+        self.resampler = filter;
+        self
+    }
+
+    pub fn mitchell_params(mut self, b: f32, c: f32) -> ImageManipulator {
+        self.mitchell_params = MitchellParams { b, c };
+        self
+    }
+
+    pub fn quantize(mut self, colors: usize, dither: bool) -> ImageManipulator {
+        self.quantize = Some(QuantizeOptions { colors, dither });
+        self
+    }
+
+    pub fn overlay(mut self, spec: OverlaySpec) -> ImageManipulator {
+        self.overlay = Some(spec);
+        self
+    }
+
+    /// Resizes `image` to exactly `width` x `height`, honoring the configured
+    /// resampler (including Mitchell–Netravali, which the `image` crate
+    /// doesn't support natively).
+    fn resize_exact(&self, image: &DynamicImage, width: u32, height: u32) -> DynamicImage {
+        match self.resampler {
+            ResampleFilter::MitchellNetravali => {
+                resampler::mitchell_resize(image, width, height, self.mitchell_params)
             }
+            filter => image.resize_exact(width, height, filter.into()),
+        }
+    }
 
-            for y in 0..height {
-                for x in 0..width {
-                    let pixel = self.image.get_pixel(x, y);
-                    bordered_image.put_pixel(x + spacing, y + spacing, pixel);
-                }
+    /// Resizes `image` so it fits within `width` x `height` while preserving
+    /// aspect ratio, honoring the configured resampler.
+    fn resize(&self, image: &DynamicImage, width: u32, height: u32) -> DynamicImage {
+        match self.resampler {
+            ResampleFilter::MitchellNetravali => {
+                let (orig_width, orig_height) = image.dimensions();
+                let scale = (width as f32 / orig_width as f32).min(height as f32 / orig_height as f32);
+                let new_width = (orig_width as f32 * scale).round().max(1.0) as u32;
+                let new_height = (orig_height as f32 * scale).round().max(1.0) as u32;
+                resampler::mitchell_resize(image, new_width, new_height, self.mitchell_params)
             }
+            filter => image.resize(width, height, filter.into()),
+        }
+    }
 
-            self.image = bordered_image;
+    fn try_add_border(&mut self) {
+        if let Some(spec) = &self.border {
+            self.image = border::add_border(&self.image, spec);
         }
     }
 
@@ -77,10 +110,7 @@ impl ImageManipulator {
         x_offset: u32,
         y_offset: u32,
     ) {
-        let mut bordered_image =
-            self.image
-                .clone()
-                .resize_exact(width, height, image::imageops::FilterType::Lanczos3);
+        let mut bordered_image = self.resize_exact(&self.image, width, height);
 
         for y in 0..height {
             for x in 0..width {
@@ -148,18 +178,28 @@ impl ImageManipulator {
 
     fn try_longest_side(&mut self) {
         if let Some(longest_side) = self.expected_longest_side {
-            self.image = self.image.resize(
-                longest_side as u32,
-                longest_side as u32,
-                image::imageops::FilterType::Lanczos3,
-            );
+            self.image = self.resize(&self.image, longest_side as u32, longest_side as u32);
         }
     }
 
+    fn try_overlay(&mut self) -> Result<(), ImageError> {
+        if let Some(spec) = &self.overlay {
+            self.image = overlay::try_apply(&self.image, spec)?;
+        }
+        Ok(())
+    }
+
     pub fn save(&mut self) -> Result<(), ImageError> {
         self.try_add_border();
         self.try_fill_aspect_ratio();
         self.try_longest_side();
+        self.try_overlay()?;
+
+        if let Some(options) = self.quantize {
+            if quantize::is_indexed_png(&self.output) {
+                return quantize::save_indexed_png(&self.image, options, &self.output);
+            }
+        }
 
         self.image.save(self.output.clone())
     }
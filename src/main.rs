@@ -1,50 +1,55 @@
 use clap::Parser;
 use cli_clipboard::{ClipboardContext, ClipboardProvider};
 use image::ImageError;
+use rayon::prelude::*;
 
+mod border;
 mod cli;
 mod image_manipulator;
+mod overlay;
+mod quantize;
+mod resampler;
 
+pub use crate::border::*;
 pub use crate::cli::*;
 pub use crate::image_manipulator::*;
+pub use crate::overlay::*;
+pub use crate::quantize::*;
+pub use crate::resampler::*;
 
 fn main() -> Result<(), ImageError> {
     let args = App::parse();
 
     match args.command {
         Command::Image(image) => {
-            let output = image.output.unwrap_or_else(|| image.input.clone());
-            let mut image_manipulator = ImageManipulator::new(image.input.clone(), output.clone())?;
-
-            // Handle --add_border
-            match image.add_border {
-                Some(Some(value)) => image_manipulator = image_manipulator.add_border(value),
-                Some(None) => image_manipulator = image_manipulator.add_border(20),
-                _ => {}
+            let inputs = expand_inputs(&image.input);
+            if inputs.is_empty() {
+                return Err(ImageError::IoError(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("no files matched --input {:?}", image.input),
+                )));
             }
+            let batch = inputs.len() > 1;
 
-            // Handle --fill_to_aspect_ratio
-            if let Some(values) = image.fill_to_aspect_ratio {
-                if values.len() == 2 {
-                    image_manipulator =
-                        image_manipulator.fill_to_aspect_ratio(values[0], values[1]);
-                } else if values.len() == 1 {
-                    image_manipulator = image_manipulator.fill_to_aspect_ratio(values[0], 1.0);
-                } else {
-                    println!(
-                        "--fill_to_aspect_ratio expects at most 2 values. Nothing will be done."
-                    );
+            if batch {
+                check_for_output_collisions(&inputs, &image.output)?;
+                if let Some(output_dir) = &image.output {
+                    std::fs::create_dir_all(output_dir)?;
                 }
             }
 
-            // Handle --longest_side
-            match image.longest_side {
-                Some(Some(value)) => image_manipulator = image_manipulator.longest_side(value),
-                Some(None) => image_manipulator = image_manipulator.longest_side(1350),
-                _ => {}
-            }
+            inputs
+                .par_iter()
+                .map(|input| {
+                    let output = if batch {
+                        batch_output_path(input, &image.output)
+                    } else {
+                        image.output.clone().unwrap_or_else(|| input.clone())
+                    };
 
-            image_manipulator.save()?;
+                    process_image(input.clone(), output, &image)
+                })
+                .collect::<Result<Vec<()>, ImageError>>()?;
         }
         Command::Caption(caption) => {
             let mut text = String::new();
@@ -83,6 +88,157 @@ fn main() -> Result<(), ImageError> {
     Ok(())
 }
 
+/// Expands glob patterns (e.g. `scans/*.tif`) in the given inputs, leaving
+/// plain paths untouched so missing files still surface their own error.
+fn expand_inputs(patterns: &[std::path::PathBuf]) -> Vec<std::path::PathBuf> {
+    let mut inputs = Vec::new();
+    for pattern in patterns {
+        let pattern_str = pattern.to_string_lossy();
+        if pattern_str.contains(['*', '?', '[']) {
+            let matches = glob::glob(&pattern_str).expect("invalid glob pattern");
+            inputs.extend(matches.filter_map(Result::ok));
+        } else {
+            inputs.push(pattern.clone());
+        }
+    }
+    inputs
+}
+
+/// The path a batch-processed `input` is written to: its basename joined
+/// onto `output_dir` (or the current directory if none was given).
+fn batch_output_path(
+    input: &std::path::Path,
+    output_dir: &Option<std::path::PathBuf>,
+) -> std::path::PathBuf {
+    let dir = output_dir
+        .clone()
+        .unwrap_or_else(|| std::path::PathBuf::from("."));
+    dir.join(input.file_name().expect("input path has no file name"))
+}
+
+/// Errors out if two inputs in the same batch would resolve to the same
+/// output path (e.g. same-named files from different source directories),
+/// rather than letting one silently clobber the other.
+fn check_for_output_collisions(
+    inputs: &[std::path::PathBuf],
+    output_dir: &Option<std::path::PathBuf>,
+) -> Result<(), ImageError> {
+    let mut seen = std::collections::HashMap::new();
+
+    for input in inputs {
+        let output = batch_output_path(input, output_dir);
+        if let Some(previous) = seen.insert(output.clone(), input.clone()) {
+            return Err(ImageError::IoError(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!(
+                    "--input {previous:?} and {input:?} would both write to {output:?}; rename one of them to avoid clobbering the other"
+                ),
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds a [`BorderSpec`] from `--add_border` and the `--border_*` flags, or
+/// `None` if no border was requested.
+fn border_spec(image: &ImageArgs) -> Option<BorderSpec> {
+    let requested = image.add_border.is_some()
+        || image.border_color.is_some()
+        || image.border_top.is_some()
+        || image.border_right.is_some()
+        || image.border_bottom.is_some()
+        || image.border_left.is_some()
+        || image.border_margin.is_some()
+        || image.border_radius.is_some();
+
+    if !requested {
+        return None;
+    }
+
+    let default_spacing = match image.add_border {
+        Some(Some(value)) => value as u32,
+        _ => 20,
+    };
+
+    // Start from the plain `--add_border N` case and layer the richer
+    // `--border_*` flags on top of it.
+    let mut spec = BorderSpec::uniform(default_spacing);
+
+    if let Some(fraction) = image.border_margin {
+        spec.width = BorderWidth::MarginFraction(fraction);
+    } else if image.border_top.is_some()
+        || image.border_right.is_some()
+        || image.border_bottom.is_some()
+        || image.border_left.is_some()
+    {
+        spec.width = BorderWidth::Sides {
+            top: image.border_top.unwrap_or(default_spacing),
+            right: image.border_right.unwrap_or(default_spacing),
+            bottom: image.border_bottom.unwrap_or(default_spacing),
+            left: image.border_left.unwrap_or(default_spacing),
+        };
+    }
+
+    if let Some(value) = &image.border_color {
+        spec.color = border::parse_color(value).unwrap_or_else(|err| panic!("{err}"));
+    }
+
+    spec.corner_radius = image.border_radius.unwrap_or(0);
+
+    Some(spec)
+}
+
+fn process_image(
+    input: std::path::PathBuf,
+    output: std::path::PathBuf,
+    image: &ImageArgs,
+) -> Result<(), ImageError> {
+    let mut image_manipulator = ImageManipulator::new(input, output)?
+        .resampler(image.filter)
+        .mitchell_params(image.mitchell_b, image.mitchell_c);
+
+    // Handle --add_border and its companion --border_* flags
+    if let Some(spec) = border_spec(image) {
+        image_manipulator = image_manipulator.border(spec);
+    }
+
+    // Handle --fill_to_aspect_ratio
+    if let Some(values) = &image.fill_to_aspect_ratio {
+        if values.len() == 2 {
+            image_manipulator = image_manipulator.fill_to_aspect_ratio(values[0], values[1]);
+        } else if values.len() == 1 {
+            image_manipulator = image_manipulator.fill_to_aspect_ratio(values[0], 1.0);
+        } else {
+            println!("--fill_to_aspect_ratio expects at most 2 values. Nothing will be done.");
+        }
+    }
+
+    // Handle --longest_side
+    match image.longest_side {
+        Some(Some(value)) => image_manipulator = image_manipulator.longest_side(value),
+        Some(None) => image_manipulator = image_manipulator.longest_side(1350),
+        _ => {}
+    }
+
+    // Handle --quantize and --dither
+    if let Some(colors) = image.quantize {
+        image_manipulator = image_manipulator.quantize(colors, image.dither);
+    }
+
+    // Handle --overlay and its companion --overlay_*/--blend_mode flags
+    if let Some(path) = &image.overlay {
+        image_manipulator = image_manipulator.overlay(OverlaySpec {
+            path: path.clone(),
+            position: image.overlay_position,
+            opacity: image.overlay_opacity,
+            blend_mode: image.blend_mode,
+        });
+    }
+
+    image_manipulator.save()
+}
+
 fn hashtags_by_film(film: &str, film_type: &FilmType, camera: &str, format: &str) -> String {
     let mut hashtags = String::new();
     match film_type {
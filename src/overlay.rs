@@ -0,0 +1,116 @@
+use image::{DynamicImage, GenericImage, GenericImageView, ImageError, Rgba};
+
+/// Where an overlay is anchored relative to the base image.
+#[derive(clap::ValueEnum, Clone, Copy, Default, Debug)]
+pub enum OverlayPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+    Center,
+}
+
+/// How an overlay's colors combine with the pixels beneath it.
+#[derive(clap::ValueEnum, Clone, Copy, Default, Debug)]
+pub enum BlendMode {
+    #[default]
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+}
+
+#[derive(Debug, Clone)]
+pub struct OverlaySpec {
+    pub path: std::path::PathBuf,
+    pub position: OverlayPosition,
+    pub opacity: f32,
+    pub blend_mode: BlendMode,
+}
+
+fn blend_channel(mode: BlendMode, base: u8, overlay: u8) -> u8 {
+    let base = base as u32;
+    let overlay = overlay as u32;
+
+    let blended = match mode {
+        BlendMode::Normal => overlay,
+        BlendMode::Multiply => base * overlay / 255,
+        BlendMode::Screen => 255 - (255 - base) * (255 - overlay) / 255,
+        BlendMode::Overlay => {
+            if base < 128 {
+                2 * base * overlay / 255
+            } else {
+                255 - 2 * (255 - base) * (255 - overlay) / 255
+            }
+        }
+    };
+
+    blended as u8
+}
+
+fn offset_for(
+    position: OverlayPosition,
+    (base_width, base_height): (u32, u32),
+    (overlay_width, overlay_height): (u32, u32),
+) -> (i64, i64) {
+    let right = base_width as i64 - overlay_width as i64;
+    let bottom = base_height as i64 - overlay_height as i64;
+
+    match position {
+        OverlayPosition::TopLeft => (0, 0),
+        OverlayPosition::TopRight => (right, 0),
+        OverlayPosition::BottomLeft => (0, bottom),
+        OverlayPosition::BottomRight => (right, bottom),
+        OverlayPosition::Center => (right / 2, bottom / 2),
+    }
+}
+
+/// Composites `overlay` onto `base` at `spec.position`, blending each
+/// overlapping pixel via `spec.blend_mode` and mixing the result into the
+/// background by the overlay's own alpha scaled by `spec.opacity`.
+pub fn apply_overlay(base: &DynamicImage, overlay: &DynamicImage, spec: &OverlaySpec) -> DynamicImage {
+    let mut result = base.clone();
+    let base_dimensions = base.dimensions();
+    let overlay_dimensions = overlay.dimensions();
+    let (x_offset, y_offset) = offset_for(spec.position, base_dimensions, overlay_dimensions);
+    let opacity = spec.opacity.clamp(0.0, 1.0);
+
+    for y in 0..overlay_dimensions.1 {
+        let dest_y = y as i64 + y_offset;
+        if dest_y < 0 || dest_y >= base_dimensions.1 as i64 {
+            continue;
+        }
+
+        for x in 0..overlay_dimensions.0 {
+            let dest_x = x as i64 + x_offset;
+            if dest_x < 0 || dest_x >= base_dimensions.0 as i64 {
+                continue;
+            }
+
+            let overlay_pixel = overlay.get_pixel(x, y);
+            let alpha = (overlay_pixel.0[3] as f32 / 255.0) * opacity;
+            if alpha <= 0.0 {
+                continue;
+            }
+
+            let base_pixel = result.get_pixel(dest_x as u32, dest_y as u32);
+            let mut blended = base_pixel.0;
+            for channel in 0..3 {
+                let mode_result = blend_channel(spec.blend_mode, base_pixel.0[channel], overlay_pixel.0[channel]);
+                blended[channel] =
+                    (base_pixel.0[channel] as f32 * (1.0 - alpha) + mode_result as f32 * alpha).round() as u8;
+            }
+
+            result.put_pixel(dest_x as u32, dest_y as u32, Rgba(blended));
+        }
+    }
+
+    result
+}
+
+/// Loads the overlay image from `spec.path` and composites it onto `base`.
+pub fn try_apply(base: &DynamicImage, spec: &OverlaySpec) -> Result<DynamicImage, ImageError> {
+    let overlay = image::open(&spec.path)?;
+    Ok(apply_overlay(base, &overlay, spec))
+}
@@ -0,0 +1,236 @@
+use image::{DynamicImage, GenericImageView, ImageError, Rgba};
+
+/// Options controlling palette quantization.
+#[derive(Debug, Clone, Copy)]
+pub struct QuantizeOptions {
+    pub colors: usize,
+    pub dither: bool,
+}
+
+/// Only PNG's indexed color type is implemented here, so quantization is
+/// only applied when the output is actually a `.png` file — writing a true
+/// indexed file is what delivers the size win; anything else would just be
+/// re-encoding a full RGBA image with fewer unique colors in it.
+pub fn is_indexed_png(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.eq_ignore_ascii_case("png"))
+        .unwrap_or(false)
+}
+
+/// A box in RGBA color space holding the pixels assigned to it, used by
+/// median-cut to recursively partition the image's colors.
+struct ColorBox {
+    pixels: Vec<[u8; 4]>,
+}
+
+impl ColorBox {
+    fn channel_range(&self, channel: usize) -> u8 {
+        let mut min = 255u8;
+        let mut max = 0u8;
+        for pixel in &self.pixels {
+            min = min.min(pixel[channel]);
+            max = max.max(pixel[channel]);
+        }
+        max - min
+    }
+
+    /// The channel (R=0, G=1, B=2, A=3) with the largest value extent, and that extent.
+    fn longest_axis(&self) -> (usize, u8) {
+        (0..4)
+            .map(|channel| (channel, self.channel_range(channel)))
+            .max_by_key(|&(_, range)| range)
+            .unwrap()
+    }
+
+    fn average_color(&self) -> Rgba<u8> {
+        let mut sums = [0u64; 4];
+        for pixel in &self.pixels {
+            for (channel, sum) in sums.iter_mut().enumerate() {
+                *sum += pixel[channel] as u64;
+            }
+        }
+        let count = self.pixels.len().max(1) as u64;
+        Rgba([
+            (sums[0] / count) as u8,
+            (sums[1] / count) as u8,
+            (sums[2] / count) as u8,
+            (sums[3] / count) as u8,
+        ])
+    }
+
+    /// Splits this box in two at the median of its longest axis.
+    fn split(mut self) -> (ColorBox, ColorBox) {
+        let (axis, _) = self.longest_axis();
+        self.pixels.sort_by_key(|pixel| pixel[axis]);
+        let right = self.pixels.split_off(self.pixels.len() / 2);
+        (ColorBox { pixels: self.pixels }, ColorBox { pixels: right })
+    }
+}
+
+/// Builds a palette of at most `palette_size` colors via median-cut:
+/// repeatedly splitting the box with the largest color extent at its median
+/// until the target count is reached (or no box can be split further).
+fn median_cut_palette(pixels: &[[u8; 4]], palette_size: usize) -> Vec<Rgba<u8>> {
+    let mut boxes = vec![ColorBox {
+        pixels: pixels.to_vec(),
+    }];
+
+    while boxes.len() < palette_size {
+        let Some(split_index) = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.pixels.len() > 1)
+            .max_by_key(|(_, b)| b.longest_axis().1)
+            .map(|(i, _)| i)
+        else {
+            break;
+        };
+
+        let (left, right) = boxes.remove(split_index).split();
+        boxes.push(left);
+        boxes.push(right);
+    }
+
+    boxes.iter().map(ColorBox::average_color).collect()
+}
+
+fn nearest_palette_index(palette: &[Rgba<u8>], pixel: [u8; 4]) -> usize {
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, color)| {
+            let mut distance = 0i32;
+            for channel in 0..4 {
+                let delta = color.0[channel] as i32 - pixel[channel] as i32;
+                distance += delta * delta;
+            }
+            distance
+        })
+        .map(|(index, _)| index)
+        .unwrap()
+}
+
+/// Spreads Floyd–Steinberg quantization error to the not-yet-visited
+/// neighbors (right, below-left, below, below-right).
+fn diffuse_error(buffer: &mut [[f32; 4]], width: u32, height: u32, x: u32, y: u32, error: [f32; 4]) {
+    const TAPS: [(i64, i64, f32); 4] = [
+        (1, 0, 7.0 / 16.0),
+        (-1, 1, 3.0 / 16.0),
+        (0, 1, 5.0 / 16.0),
+        (1, 1, 1.0 / 16.0),
+    ];
+
+    for (dx, dy, weight) in TAPS {
+        let nx = x as i64 + dx;
+        let ny = y as i64 + dy;
+        if nx < 0 || ny < 0 || nx >= width as i64 || ny >= height as i64 {
+            continue;
+        }
+
+        let neighbor = &mut buffer[(ny as u32 * width + nx as u32) as usize];
+        for (channel, value) in neighbor.iter_mut().enumerate() {
+            *value += error[channel] * weight;
+        }
+    }
+}
+
+/// An image reduced to palette indices, ready to be written out as a true
+/// indexed file.
+struct QuantizedImage {
+    width: u32,
+    height: u32,
+    indices: Vec<u8>,
+    palette: Vec<Rgba<u8>>,
+}
+
+/// Reduces `image` to at most `options.colors` palette entries (the count is
+/// clamped to 1..=256) via median-cut, returning per-pixel palette indices rather than reconstructed
+/// RGBA pixels. Optionally diffuses quantization error with Floyd–Steinberg
+/// dithering to avoid banding in gradients. Alpha is quantized alongside RGB
+/// so it survives into the palette.
+fn build_indexed(image: &DynamicImage, options: QuantizeOptions) -> QuantizedImage {
+    let rgba = image.to_rgba8();
+    let (width, height) = rgba.dimensions();
+    let pixels: Vec<[u8; 4]> = rgba.pixels().map(|pixel| pixel.0).collect();
+    let palette = median_cut_palette(&pixels, options.colors.clamp(1, 256));
+
+    let mut indices = vec![0u8; pixels.len()];
+
+    if options.dither {
+        let mut errors: Vec<[f32; 4]> = vec![[0.0; 4]; pixels.len()];
+
+        for y in 0..height {
+            for x in 0..width {
+                let i = (y * width + x) as usize;
+                let pixel = pixels[i];
+                let mut adjusted = [0u8; 4];
+                for channel in 0..4 {
+                    adjusted[channel] = (pixel[channel] as f32 + errors[i][channel])
+                        .round()
+                        .clamp(0.0, 255.0) as u8;
+                }
+
+                let palette_index = nearest_palette_index(&palette, adjusted);
+                indices[i] = palette_index as u8;
+
+                let chosen = palette[palette_index];
+                let mut error = [0.0f32; 4];
+                for channel in 0..4 {
+                    error[channel] = adjusted[channel] as f32 - chosen.0[channel] as f32;
+                }
+                diffuse_error(&mut errors, width, height, x, y, error);
+            }
+        }
+    } else {
+        for (i, pixel) in pixels.iter().enumerate() {
+            indices[i] = nearest_palette_index(&palette, *pixel) as u8;
+        }
+    }
+
+    QuantizedImage {
+        width,
+        height,
+        indices,
+        palette,
+    }
+}
+
+fn io_error(err: impl std::fmt::Display) -> ImageError {
+    ImageError::IoError(std::io::Error::new(std::io::ErrorKind::Other, err.to_string()))
+}
+
+/// Quantizes `image` and writes it to `path` as a true 8-bit indexed PNG
+/// (a `PLTE` palette plus, when any palette entry isn't fully opaque, a
+/// `tRNS` transparency chunk), which is what actually shrinks the file
+/// compared to a full RGBA encode.
+pub fn save_indexed_png(
+    image: &DynamicImage,
+    options: QuantizeOptions,
+    path: &std::path::Path,
+) -> Result<(), ImageError> {
+    let quantized = build_indexed(image, options);
+
+    let file = std::fs::File::create(path).map_err(ImageError::IoError)?;
+    let writer = std::io::BufWriter::new(file);
+
+    let mut encoder = png::Encoder::new(writer, quantized.width, quantized.height);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut rgb_palette = Vec::with_capacity(quantized.palette.len() * 3);
+    let mut alpha_palette = Vec::with_capacity(quantized.palette.len());
+    for color in &quantized.palette {
+        rgb_palette.extend_from_slice(&color.0[..3]);
+        alpha_palette.push(color.0[3]);
+    }
+    encoder.set_palette(rgb_palette);
+    if alpha_palette.iter().any(|&alpha| alpha != 255) {
+        encoder.set_trns(alpha_palette);
+    }
+
+    let mut writer = encoder.write_header().map_err(io_error)?;
+    writer.write_image_data(&quantized.indices).map_err(io_error)?;
+
+    Ok(())
+}